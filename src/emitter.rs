@@ -0,0 +1,378 @@
+use piston_window::*;
+use rand::prelude::*;
+
+use crate::{scene, ColorRamp, Handle, Particle, World};
+
+/// Where newly spawned particles appear relative to `Emitter::origin`.
+#[derive(Debug, Clone, Copy)]
+pub enum EmitShape {
+    Point,
+    /// A segment from `origin + a` to `origin + b`.
+    Line([f64; 2], [f64; 2]),
+    Disc { radius: f64 },
+}
+
+/// How a spawned particle's initial velocity is sampled.
+#[derive(Debug, Clone, Copy)]
+pub enum VelocityMode {
+    /// Uniform in `[-strength, strength]` on each axis, as the original
+    /// explosion effect did.
+    UniformBox { strength: f64 },
+    /// Outward from the emitter's origin with a speed in `speed` and an
+    /// angular spread (radians) around the point-to-spawn direction.
+    Radial { speed: (f64, f64), spread: f64 },
+}
+
+/// A generalized particle source: one-shot bursts (`trigger`) or continuous
+/// emission at a configurable particles-per-second rate (`step`), over a
+/// configurable spawn shape and velocity distribution. Holds no particle
+/// data itself; spawned particles live in the `World` it's given.
+pub struct Emitter {
+    pub members: Vec<Handle>,
+    pub origin: [f64; 2],
+    pub shape: EmitShape,
+    pub velocity: VelocityMode,
+    pub burst_count: usize,
+    pub rate: f64,
+    pub lifespan: f64,
+    pub color: Option<[f32; 4]>,
+    pub ramp: Option<ColorRamp>,
+    accumulator: f64,
+}
+
+impl Emitter {
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+            origin: [0.0, 0.0],
+            shape: EmitShape::Point,
+            velocity: VelocityMode::UniformBox { strength: 0.0 },
+            burst_count: 50,
+            rate: 0.0,
+            lifespan: 0.5,
+            color: None,
+            ramp: None,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Builds an emitter from a scene file's `EmitterConfig`.
+    pub fn from_config(config: &scene::EmitterConfig) -> Self {
+        let shape = match config.shape {
+            scene::ShapeConfig::Point => EmitShape::Point,
+            scene::ShapeConfig::Line { a, b } => EmitShape::Line(a, b),
+            scene::ShapeConfig::Disc { radius } => EmitShape::Disc { radius },
+        };
+        let velocity = match config.velocity {
+            scene::VelocityConfig::UniformBox => VelocityMode::UniformBox {
+                strength: config.strength,
+            },
+            scene::VelocityConfig::Radial {
+                speed_min,
+                speed_max,
+                spread,
+            } => VelocityMode::Radial {
+                speed: (speed_min, speed_max),
+                spread,
+            },
+        };
+        Self::new()
+            .with_origin(config.origin)
+            .with_shape(shape)
+            .with_velocity(velocity)
+            .with_burst_count(config.count)
+            .with_rate(config.rate)
+            .with_lifespan(config.fade_ms as f64 / 1000.0)
+            .with_color(config.color)
+    }
+
+    pub fn with_origin(self, origin: [f64; 2]) -> Self {
+        Self { origin, ..self }
+    }
+
+    pub fn with_shape(self, shape: EmitShape) -> Self {
+        Self { shape, ..self }
+    }
+
+    pub fn with_velocity(self, velocity: VelocityMode) -> Self {
+        Self { velocity, ..self }
+    }
+
+    pub fn with_burst_count(self, burst_count: usize) -> Self {
+        Self {
+            burst_count,
+            ..self
+        }
+    }
+
+    /// Particles spawned per second when driven continuously via `step`.
+    pub fn with_rate(self, rate: f64) -> Self {
+        Self { rate, ..self }
+    }
+
+    /// Lifespan in seconds given to every particle this emitter spawns.
+    pub fn with_lifespan(self, lifespan: f64) -> Self {
+        Self { lifespan, ..self }
+    }
+
+    pub fn with_color(self, color: Option<[f32; 4]>) -> Self {
+        Self { color, ..self }
+    }
+
+    pub fn with_ramp(self, ramp: ColorRamp) -> Self {
+        Self {
+            ramp: Some(ramp),
+            ..self
+        }
+    }
+
+    /// Applies `ramp` only if this emitter has no explicit `color` or
+    /// `ramp` of its own, so a caller can layer on a default effect preset
+    /// (e.g. `ColorRamp::fire()`) without silently overriding a
+    /// scene-configured color.
+    pub fn with_default_ramp(self, ramp: ColorRamp) -> Self {
+        if self.color.is_some() || self.ramp.is_some() {
+            self
+        } else {
+            self.with_ramp(ramp)
+        }
+    }
+
+    fn spawn_point(&self) -> [f64; 2] {
+        match self.shape {
+            EmitShape::Point => self.origin,
+            EmitShape::Line(a, b) => {
+                let t: f64 = rand::thread_rng().gen();
+                [
+                    self.origin[0] + a[0] + (b[0] - a[0]) * t,
+                    self.origin[1] + a[1] + (b[1] - a[1]) * t,
+                ]
+            }
+            EmitShape::Disc { radius } => {
+                let angle = rand::thread_rng().gen_range(0.0..std::f64::consts::TAU);
+                let r = radius * rand::thread_rng().gen::<f64>().sqrt();
+                [
+                    self.origin[0] + angle.cos() * r,
+                    self.origin[1] + angle.sin() * r,
+                ]
+            }
+        }
+    }
+
+    fn spawn_velocity(&self, point: [f64; 2]) -> [f64; 2] {
+        match self.velocity {
+            VelocityMode::UniformBox { strength } => [
+                rand::thread_rng().gen_range(-strength..=strength),
+                rand::thread_rng().gen_range(-strength..=strength),
+            ],
+            VelocityMode::Radial { speed, spread } => {
+                let base_angle = (point[1] - self.origin[1]).atan2(point[0] - self.origin[0]);
+                let angle =
+                    base_angle + rand::thread_rng().gen_range(-spread / 2.0..spread / 2.0);
+                let mag = rand::thread_rng().gen_range(speed.0..speed.1);
+                [angle.cos() * mag, angle.sin() * mag]
+            }
+        }
+    }
+
+    /// Spawns `count` particles into `world` immediately, e.g. a burst or a
+    /// continuous emission's per-tick quota. Returns the newly spawned
+    /// handles so a caller (e.g. `GravityHandler`) can register only what's
+    /// new instead of re-scanning the whole member list.
+    fn spawn(&mut self, world: &mut World, count: usize) -> Vec<Handle> {
+        let mut spawned = Vec::with_capacity(count);
+        for _ in 0..count {
+            let point = self.spawn_point();
+            let velocity = self.spawn_velocity(point);
+            let mut particle = Particle::new(point)
+                .with_velocity(velocity)
+                .with_lifespan(self.lifespan);
+            if let Some(ramp) = &self.ramp {
+                particle = particle.with_ramp(ramp.clone());
+            } else if let Some(color) = self.color {
+                particle.color = color;
+            }
+            let handle = world.spawn(particle);
+            self.members.push(handle);
+            spawned.push(handle);
+        }
+        spawned
+    }
+
+    /// Fires `burst_count` particles at once, e.g. an explosion. Returns the
+    /// newly spawned handles.
+    pub fn trigger(&mut self, world: &mut World) -> Vec<Handle> {
+        self.spawn(world, self.burst_count)
+    }
+
+    /// Advances continuous emission by `dt` seconds, accumulating a
+    /// fractional particle count so a rate like 12.5/s still emits
+    /// correctly regardless of the tick rate, then drops members whose
+    /// particle has since deactivated. Returns any newly spawned handles.
+    /// Does not integrate positions; that happens once per tick in
+    /// `World::integrate`.
+    pub fn step(&mut self, world: &mut World, dt: f64) -> Vec<Handle> {
+        let spawned = if self.rate > 0.0 {
+            self.accumulator += self.rate * dt;
+            let to_spawn = self.accumulator.floor();
+            if to_spawn >= 1.0 {
+                self.accumulator -= to_spawn;
+                self.spawn(world, to_spawn as usize)
+            } else {
+                Vec::new()
+            }
+        } else {
+            Vec::new()
+        };
+
+        self.members.retain(|&handle| world.is_alive(handle));
+        spawned
+    }
+
+    pub fn draw<G: graphics::Graphics>(
+        &self,
+        world: &World,
+        draw_state: &DrawState,
+        transform: [[f64; 3]; 2],
+        g: &mut G,
+    ) {
+        for &handle in &self.members {
+            if let Some(particle) = world.get(handle) {
+                particle.draw(draw_state, transform, g);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_accumulates_fractional_particles_across_variable_dt() {
+        let mut world = World::new();
+        let mut emitter = Emitter::new().with_rate(12.5);
+
+        // Emission is driven off a real-time accumulator, not a hard-coded
+        // tick duration, so two 0.5 s steps must emit exactly as many
+        // particles as one combined 1.0 s step would.
+        let spawned_a = emitter.step(&mut world, 0.5);
+        let spawned_b = emitter.step(&mut world, 0.5);
+        assert_eq!(spawned_a.len() + spawned_b.len(), 12);
+
+        let mut world = World::new();
+        let mut emitter = Emitter::new().with_rate(12.5);
+        let spawned = emitter.step(&mut world, 1.0);
+        assert_eq!(spawned.len(), 12);
+    }
+
+    #[test]
+    fn rate_of_zero_never_spawns() {
+        let mut world = World::new();
+        let mut emitter = Emitter::new();
+        assert!(emitter.step(&mut world, 10.0).is_empty());
+    }
+
+    #[test]
+    fn from_config_wires_shape_and_velocity_mode() {
+        let config = scene::EmitterConfig {
+            origin: [1.0, 2.0],
+            strength: 3.0,
+            count: 5,
+            rate: 0.0,
+            fade_ms: 500,
+            color: None,
+            shape: scene::ShapeConfig::Disc { radius: 7.0 },
+            velocity: scene::VelocityConfig::Radial {
+                speed_min: 1.0,
+                speed_max: 2.0,
+                spread: 0.5,
+            },
+        };
+
+        let emitter = Emitter::from_config(&config);
+        assert!(matches!(
+            emitter.shape,
+            EmitShape::Disc { radius } if radius == 7.0
+        ));
+        assert!(matches!(
+            emitter.velocity,
+            VelocityMode::Radial { speed: (1.0, 2.0), spread } if spread == 0.5
+        ));
+    }
+
+    #[test]
+    fn from_config_defaults_to_the_original_point_uniform_box_explosion() {
+        let config = scene::EmitterConfig {
+            origin: [0.0, 0.0],
+            strength: 2.0,
+            count: 50,
+            rate: 0.0,
+            fade_ms: 500,
+            color: None,
+            shape: scene::ShapeConfig::default(),
+            velocity: scene::VelocityConfig::default(),
+        };
+
+        let emitter = Emitter::from_config(&config);
+        assert!(matches!(emitter.shape, EmitShape::Point));
+        assert!(matches!(
+            emitter.velocity,
+            VelocityMode::UniformBox { strength } if strength == 2.0
+        ));
+    }
+
+    #[test]
+    fn from_config_count_and_color_reach_spawned_particles() {
+        let config = scene::EmitterConfig {
+            origin: [0.0, 0.0],
+            strength: 1.0,
+            count: 7,
+            rate: 0.0,
+            fade_ms: 500,
+            color: Some([0.2, 0.4, 0.6, 1.0]),
+            shape: scene::ShapeConfig::default(),
+            velocity: scene::VelocityConfig::default(),
+        };
+
+        let mut world = World::new();
+        let mut emitter = Emitter::from_config(&config);
+        let spawned = emitter.trigger(&mut world);
+
+        assert_eq!(spawned.len(), 7);
+        for handle in spawned {
+            assert_eq!(world.get(handle).unwrap().color, [0.2, 0.4, 0.6, 1.0]);
+        }
+    }
+
+    #[test]
+    fn with_default_ramp_does_not_override_a_configured_color() {
+        let config = scene::EmitterConfig {
+            origin: [0.0, 0.0],
+            strength: 1.0,
+            count: 3,
+            rate: 0.0,
+            fade_ms: 500,
+            color: Some([0.2, 0.4, 0.6, 1.0]),
+            shape: scene::ShapeConfig::default(),
+            velocity: scene::VelocityConfig::default(),
+        };
+
+        // Mirrors main's `Emitter::from_config(&config).with_default_ramp(..)`
+        // wiring: a scene-configured color must win over a fallback preset
+        // ramp applied on top of it.
+        let mut emitter = Emitter::from_config(&config).with_default_ramp(ColorRamp::fire());
+        assert!(emitter.ramp.is_none());
+
+        let mut world = World::new();
+        for handle in emitter.trigger(&mut world) {
+            assert_eq!(world.get(handle).unwrap().color, [0.2, 0.4, 0.6, 1.0]);
+        }
+    }
+
+    #[test]
+    fn with_default_ramp_applies_when_nothing_else_is_configured() {
+        let emitter = Emitter::new().with_default_ramp(ColorRamp::fire());
+        assert!(emitter.ramp.is_some());
+    }
+}
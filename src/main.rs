@@ -1,15 +1,117 @@
 extern crate piston_window;
-extern crate tokio;
 
-use std::{
-    ops::Index,
-    sync::{Arc, Mutex},
-};
+mod emitter;
+mod scene;
+mod script;
+mod world;
+
+use std::{cmp::Reverse, collections::BinaryHeap};
 
 use piston_window::*;
 use rand::prelude::*;
 
-use tokio::{sync::oneshot, task::*, time::*};
+pub use world::{Handle, World};
+
+/// An ordered set of `(t_normalized, rgba)` stops describing how a
+/// particle's color evolves over its lifetime. `sample` linearly
+/// interpolates between the two stops bracketing `t`.
+#[derive(Debug, Clone)]
+pub struct ColorRamp {
+    stops: Vec<(f32, [f32; 4])>,
+}
+
+impl ColorRamp {
+    pub fn new(mut stops: Vec<(f32, [f32; 4])>) -> Self {
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Self { stops }
+    }
+
+    pub fn sample(&self, t: f32) -> [f32; 4] {
+        let t = t.clamp(0.0, 1.0);
+        let stops = &self.stops;
+        if stops.is_empty() {
+            return [1.0, 1.0, 1.0, 1.0];
+        }
+        if t <= stops[0].0 {
+            return stops[0].1;
+        }
+        for window in stops.windows(2) {
+            let (t0, c0) = window[0];
+            let (t1, c1) = window[1];
+            if t >= t0 && t <= t1 {
+                let f = (t - t0) / (t1 - t0).max(f32::EPSILON);
+                return [
+                    c0[0] + (c1[0] - c0[0]) * f,
+                    c0[1] + (c1[1] - c0[1]) * f,
+                    c0[2] + (c1[2] - c0[2]) * f,
+                    c0[3] + (c1[3] - c0[3]) * f,
+                ];
+            }
+        }
+        stops.last().unwrap().1
+    }
+
+    /// White -> orange -> dark smoke -> transparent, for explosion fade-outs.
+    pub fn fire() -> Self {
+        Self::new(vec![
+            (0.0, [1.0, 1.0, 1.0, 1.0]),
+            (0.25, [1.0, 0.6, 0.1, 1.0]),
+            (0.7, [0.2, 0.15, 0.15, 0.8]),
+            (1.0, [0.05, 0.05, 0.05, 0.0]),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod color_ramp_tests {
+    use super::*;
+
+    #[test]
+    fn samples_exactly_on_a_stop() {
+        let ramp = ColorRamp::new(vec![(0.0, [1.0, 0.0, 0.0, 1.0]), (1.0, [0.0, 0.0, 1.0, 1.0])]);
+        assert_eq!(ramp.sample(0.0), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(ramp.sample(1.0), [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn linearly_interpolates_between_bracketing_stops() {
+        let ramp = ColorRamp::new(vec![(0.0, [0.0, 0.0, 0.0, 0.0]), (1.0, [1.0, 1.0, 1.0, 1.0])]);
+        assert_eq!(ramp.sample(0.5), [0.5, 0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn clamps_and_holds_past_the_last_stop() {
+        let ramp = ColorRamp::fire();
+        assert_eq!(ramp.sample(2.0), ramp.sample(1.0));
+        assert_eq!(ramp.sample(-1.0), ramp.sample(0.0));
+    }
+
+    #[test]
+    fn particle_lifecycle_is_driven_by_accumulated_dt_not_call_count() {
+        // A particle's fade/death used to be driven by a wall-clock tokio
+        // timer racing against a separately-ticking render loop, so calling
+        // `run` at a different rate than the timer fired could leave it
+        // alive past its ramp finishing (or vice versa). Now age/lifespan
+        // and the ramp both advance off the same `dt`, so the two stay in
+        // lockstep no matter how many fixed steps it takes to cover a given
+        // span of simulated time.
+        let ramp = ColorRamp::fire();
+        let mut one_big_step = Particle::new([0.0, 0.0])
+            .with_lifespan(1.0)
+            .with_ramp(ramp.clone());
+        one_big_step.run(1.0);
+
+        let mut many_small_steps = Particle::new([0.0, 0.0])
+            .with_lifespan(1.0)
+            .with_ramp(ramp);
+        for _ in 0..4 {
+            many_small_steps.run(0.25);
+        }
+
+        assert_eq!(one_big_step.active, many_small_steps.active);
+        assert_eq!(one_big_step.color, many_small_steps.color);
+    }
+}
 
 #[derive(Debug)]
 pub struct Particle {
@@ -19,6 +121,10 @@ pub struct Particle {
     radius: f64,
     pub active: bool,
     pub color: [f32; 4],
+    collision_count: u64,
+    age: f64,
+    lifespan: Option<f64>,
+    ramp: Option<ColorRamp>,
 }
 
 impl Particle {
@@ -35,6 +141,10 @@ impl Particle {
                 rand::thread_rng().gen(),
                 1.0,
             ],
+            collision_count: 0,
+            age: 0.0,
+            lifespan: None,
+            ramp: None,
         }
     }
 
@@ -42,20 +152,54 @@ impl Particle {
         Self { velocity, ..self }
     }
 
+    /// Gives the particle a finite lifespan in seconds. Once `age` passes
+    /// `lifespan` the particle deactivates.
+    pub fn with_lifespan(self, lifespan: f64) -> Self {
+        Self {
+            lifespan: Some(lifespan),
+            ..self
+        }
+    }
+
+    /// Attaches a color ramp sampled by `age / lifespan` each tick. Requires
+    /// `with_lifespan` to also be set, otherwise the ramp never advances.
+    pub fn with_ramp(self, ramp: ColorRamp) -> Self {
+        Self {
+            ramp: Some(ramp),
+            ..self
+        }
+    }
+
     pub fn push(&mut self, acceleration: [f64; 2]) {
         self.acceleration[0] += acceleration[0];
         self.acceleration[1] += acceleration[1];
     }
 
-    pub fn run(&mut self) {
-        self.velocity[0] += self.acceleration[0];
-        self.velocity[1] += self.acceleration[1];
+    /// Integrates one fixed timestep of `dt` seconds. Scaling the velocity
+    /// and position updates by `dt` keeps motion deterministic regardless
+    /// of how many substeps a frame happens to run.
+    pub fn run(&mut self, dt: f64) {
+        self.velocity[0] += self.acceleration[0] * dt;
+        self.velocity[1] += self.acceleration[1] * dt;
 
-        self.point[0] += self.velocity[0];
-        self.point[1] += self.velocity[1];
+        self.point[0] += self.velocity[0] * dt;
+        self.point[1] += self.velocity[1] * dt;
 
         self.acceleration[0] = 0.0;
         self.acceleration[1] = 0.0;
+
+        self.age += dt;
+        if let Some(lifespan) = self.lifespan {
+            let t = (self.age / lifespan) as f32;
+            if let Some(ramp) = &self.ramp {
+                self.color = ramp.sample(t);
+            } else if t >= 1.0 {
+                self.color[3] = 0.0;
+            }
+            if t >= 1.0 {
+                self.active = false;
+            }
+        }
     }
 
     pub fn draw<G>(&self, draw_state: &DrawState, transform: [[f64; 3]; 2], g: &mut G)
@@ -83,65 +227,442 @@ impl Particle {
             self.point[1] + self.radius,
         ]
     }
+
+    pub fn age(&self) -> f64 {
+        self.age
+    }
 }
 
-pub struct GravityHandler {
-    pub entities: Vec<Arc<Mutex<Particle>>>,
+/// An event time wrapper giving `f64` a total order so `Event` can live in a
+/// `BinaryHeap`. Collision times are never `NaN`, so this is safe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EventTime(f64);
+
+impl Eq for EventTime {}
+
+impl PartialOrd for EventTime {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-impl GravityHandler {
+impl Ord for EventTime {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+/// A predicted collision between particles `i` and `j` at `time`. The
+/// `collision_count_*` fields snapshot each particle's collision counter at
+/// scheduling time; if either has since changed, the event is stale and must
+/// be discarded rather than acted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Event {
+    time: EventTime,
+    i: Handle,
+    j: Handle,
+    collision_count_i: u64,
+    collision_count_j: u64,
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.time.cmp(&other.time)
+    }
+}
+
+/// Predicts and resolves pairwise particle-particle collisions analytically
+/// instead of scanning for overlap every frame. `i`/`j` below are `World`
+/// slots directly (not positions into `self.slots`), so membership changes
+/// never invalidate an already-scheduled event. Tracks which slots are
+/// currently members in `tracked` for O(1) staleness checks and keeps a
+/// min-heap of the next predicted collision time for every pair that is
+/// still approaching.
+pub struct CollisionScheduler {
+    slots: Vec<Handle>,
+    tracked: std::collections::HashSet<Handle>,
+    events: BinaryHeap<Reverse<Event>>,
+}
+
+impl CollisionScheduler {
     pub fn new() -> Self {
         Self {
-            entities: Vec::new(),
+            slots: Vec::new(),
+            tracked: std::collections::HashSet::new(),
+            events: BinaryHeap::new(),
         }
     }
 
-    pub fn run(&mut self) {
-        self.entities.retain(|test| test.lock().unwrap().active);
-        self.entities.iter().for_each(|entity| {
-            let rc = entity.clone();
-            let mut ent = rc.lock().unwrap();
-            ent.push([0.0, 0.098]);
-            ent.run();
-        });
+    /// Solves `(dv.dv)t^2 + 2(dp.dv)t + (dp.dp - R^2) = 0` for the smaller
+    /// positive root, returning `None` if either handle's slot has been
+    /// recycled or if the particles are moving apart or never meet.
+    fn predict(&self, world: &World, i: Handle, j: Handle) -> Option<f64> {
+        let pi = world.get(i)?;
+        let pj = world.get(j)?;
+
+        let dp = [pj.point[0] - pi.point[0], pj.point[1] - pi.point[1]];
+        let dv = [pj.velocity[0] - pi.velocity[0], pj.velocity[1] - pi.velocity[1]];
+        let r = pi.radius + pj.radius;
+
+        let a = dv[0] * dv[0] + dv[1] * dv[1];
+        let b = 2.0 * (dp[0] * dv[0] + dp[1] * dv[1]);
+        let c = dp[0] * dp[0] + dp[1] * dp[1] - r * r;
+
+        if a == 0.0 || b >= 0.0 {
+            // Not moving, or separating (dp.dv >= 0).
+            return None;
+        }
+
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let t = (-b - discriminant.sqrt()) / (2.0 * a);
+        if t > 0.0 {
+            Some(t)
+        } else {
+            None
+        }
     }
 
-    pub fn draw<G>(&mut self, draw_state: &DrawState, transform: [[f64; 3]; 2], g: &mut G)
-    where
-        G: graphics::Graphics,
-    {
-        self.entities.iter().for_each(|particle| {
-            let rc = particle.clone();
-            let part = rc.lock().unwrap();
+    /// Schedules `i`/`j` if they're on a collision course, storing the
+    /// event's deadline as an absolute `world.time`-based instant (not a
+    /// duration from "now") so it stays meaningful no matter how many ticks
+    /// pass before `step` gets around to it.
+    fn schedule_pair(&mut self, world: &World, i: Handle, j: Handle) {
+        let (i, j) = if i.slot() < j.slot() { (i, j) } else { (j, i) };
+        if i.slot() == j.slot() {
+            return;
+        }
+        if let Some(t) = self.predict(world, i, j) {
+            let collision_count_i = world.get(i).unwrap().collision_count;
+            let collision_count_j = world.get(j).unwrap().collision_count;
+            self.events.push(Reverse(Event {
+                time: EventTime(world.time + t),
+                i,
+                j,
+                collision_count_i,
+                collision_count_j,
+            }));
+        }
+    }
+
+    /// Registers newly spawned `World` slots as members, scheduling
+    /// collisions between them and every already-tracked slot (and each
+    /// other). Already-scheduled events for existing members are untouched,
+    /// so this is cheap to call every time new particles appear instead of
+    /// rebuilding the whole pair set.
+    pub fn add_members(&mut self, world: &World, new_slots: &[Handle]) {
+        // Snapshot membership before mutating self.slots below: schedule_pair
+        // takes &mut self, so it can't run from inside a loop still borrowing
+        // self.slots.
+        let existing = self.slots.clone();
+        for (idx, &slot) in new_slots.iter().enumerate() {
+            for &other in &existing {
+                self.schedule_pair(world, slot, other);
+            }
+            for &other in &new_slots[idx + 1..] {
+                self.schedule_pair(world, slot, other);
+            }
+        }
+        self.slots.extend_from_slice(new_slots);
+        self.tracked.extend(new_slots.iter().copied());
+    }
 
-            part.draw(draw_state, transform, g);
+    /// Drops members whose particle has since deactivated or whose slot was
+    /// recycled out from under them. Events referring to a dropped handle
+    /// are left in the heap but are skipped in `step` once it's no longer
+    /// tracked.
+    pub fn remove_dead(&mut self, world: &World) {
+        let tracked = &mut self.tracked;
+        self.slots.retain(|&handle| {
+            let alive = world.is_alive(handle);
+            if !alive {
+                tracked.remove(&handle);
+            }
+            alive
         });
     }
 
-    pub fn spawn_one(&mut self, point: [f64; 2]) -> Arc<Mutex<Particle>> {
-        let new = Arc::new(Mutex::new(Particle::new(point).with_velocity([
-            rand::thread_rng().gen_range(-1.0..1.0),
-            rand::thread_rng().gen_range(-1.0..1.0),
-        ])));
-        self.entities.push(new.clone());
+    /// Resolve velocities along the contact normal (elastic exchange of the
+    /// normal component) and bump both collision counters.
+    fn resolve(&mut self, world: &mut World, i: Handle, j: Handle) {
+        let (Some(pi), Some(pj)) = (world.get(i), world.get(j)) else {
+            return;
+        };
+
+        let dp = [pj.point[0] - pi.point[0], pj.point[1] - pi.point[1]];
+        let dist = (dp[0] * dp[0] + dp[1] * dp[1]).sqrt();
+        if dist == 0.0 {
+            return;
+        }
+        let normal = [dp[0] / dist, dp[1] / dist];
 
-        let (tx, rx) = oneshot::channel();
+        let dv = [pj.velocity[0] - pi.velocity[0], pj.velocity[1] - pi.velocity[1]];
+        let approach = dv[0] * normal[0] + dv[1] * normal[1];
+        if approach >= 0.0 {
+            return;
+        }
 
-        let rc = new.clone();
+        let pi = world.get_mut(i).unwrap();
+        pi.velocity[0] += approach * normal[0];
+        pi.velocity[1] += approach * normal[1];
+        pi.collision_count += 1;
 
-        let rand: f64 = rand::thread_rng().gen::<f64>() * 5000.0;
+        let pj = world.get_mut(j).unwrap();
+        pj.velocity[0] -= approach * normal[0];
+        pj.velocity[1] -= approach * normal[1];
+        pj.collision_count += 1;
+    }
 
-        tokio::spawn(async move {
-            tokio::time::sleep(Duration::from_millis(rand as u64)).await;
-            tx.send(rc).unwrap();
-        });
+    /// Process every event whose absolute deadline falls within `horizon`
+    /// seconds of the current `world.time` (i.e. within the tick about to
+    /// run), skipping stale and no-longer-tracked events and re-predicting
+    /// collisions for any particle whose velocity just changed. Deadlines
+    /// further out are left in the heap for a future tick to pick up —
+    /// they're absolute instants, not durations, so they don't need
+    /// refreshing while they wait.
+    pub fn step(&mut self, world: &mut World, horizon: f64) {
+        let deadline = world.time + horizon;
+        while let Some(&Reverse(event)) = self.events.peek() {
+            if event.time.0 > deadline {
+                break;
+            }
+            self.events.pop();
 
-        tokio::spawn(async move {
-            let part = rx.await.unwrap();
-            part.lock().unwrap().active = false;
-        });
+            if !self.tracked.contains(&event.i) || !self.tracked.contains(&event.j) {
+                continue;
+            }
+
+            let stale = match (world.get(event.i), world.get(event.j)) {
+                (Some(pi), Some(pj)) => {
+                    pi.collision_count != event.collision_count_i
+                        || pj.collision_count != event.collision_count_j
+                }
+                _ => true,
+            };
+            if stale {
+                continue;
+            }
+
+            self.resolve(world, event.i, event.j);
 
-        new.clone()
+            // Snapshot before re-scheduling: schedule_pair takes &mut self,
+            // so it can't run from inside a loop still borrowing self.slots.
+            let members = self.slots.clone();
+            for k in members {
+                if k != event.i {
+                    self.schedule_pair(world, event.i, k);
+                }
+                if k != event.j {
+                    self.schedule_pair(world, event.j, k);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod collision_scheduler_tests {
+    use super::*;
+
+    fn head_on_pair() -> (World, Handle, Handle) {
+        let mut world = World::new();
+        let a = world.spawn(Particle::new([0.0, 0.0]).with_velocity([1.0, 0.0]));
+        let b = world.spawn(Particle::new([10.0, 0.0]).with_velocity([-1.0, 0.0]));
+        (world, a, b)
+    }
+
+    #[test]
+    fn predicts_and_resolves_a_head_on_collision() {
+        let (mut world, a, b) = head_on_pair();
+        let mut scheduler = CollisionScheduler::new();
+        scheduler.add_members(&world, &[a, b]);
+
+        // Radius defaults to 1.0 each, so they meet when 8 units apart have
+        // closed at a combined 2 units/sec: t = (10 - 2) / 2 = 4.
+        scheduler.step(&mut world, 4.0);
+
+        assert_eq!(world.get(a).unwrap().collision_count, 1);
+        assert_eq!(world.get(b).unwrap().collision_count, 1);
+        // An elastic head-on bounce between equal "masses" swaps velocities.
+        assert!(world.get(a).unwrap().velocity[0] < 0.0);
+        assert!(world.get(b).unwrap().velocity[0] > 0.0);
+    }
+
+    #[test]
+    fn ignores_events_for_members_that_have_been_removed() {
+        let (mut world, a, b) = head_on_pair();
+        let mut scheduler = CollisionScheduler::new();
+        scheduler.add_members(&world, &[a, b]);
+
+        world.get_mut(b).unwrap().active = false;
+        scheduler.remove_dead(&world);
+        scheduler.step(&mut world, 100.0);
+
+        assert_eq!(world.get(a).unwrap().collision_count, 0);
+    }
+
+    #[test]
+    fn ignores_events_for_a_handle_whose_slot_was_recycled() {
+        let (mut world, a, b) = head_on_pair();
+        let mut scheduler = CollisionScheduler::new();
+        scheduler.add_members(&world, &[a, b]);
+
+        // Kill and recycle `b`'s slot into an unrelated, stationary particle
+        // before the scheduled collision would fire.
+        world.get_mut(b).unwrap().active = false;
+        world.integrate();
+        let stand_in = world.spawn(Particle::new([10.0, 0.0]));
+        assert_eq!(stand_in.slot(), b.slot());
+
+        scheduler.remove_dead(&world);
+        scheduler.step(&mut world, 100.0);
+
+        assert_eq!(world.get(a).unwrap().collision_count, 0);
+        assert_eq!(world.get(stand_in).unwrap().collision_count, 0);
+    }
+
+    #[test]
+    fn add_members_schedules_pairs_against_already_tracked_members() {
+        let (mut world, a, b) = head_on_pair();
+        let mut scheduler = CollisionScheduler::new();
+        scheduler.add_members(&world, &[a]);
+        scheduler.add_members(&world, &[b]);
+
+        scheduler.step(&mut world, 4.0);
+
+        assert_eq!(world.get(a).unwrap().collision_count, 1);
+        assert_eq!(world.get(b).unwrap().collision_count, 1);
+    }
+
+    #[test]
+    fn a_collision_predicted_many_ticks_out_still_fires() {
+        // Reproduces the production call pattern: step() with the fixed
+        // per-tick dt as `horizon`, then integrate(), every tick. A
+        // collision predicted several seconds out must still eventually
+        // resolve -- that's the entire point of scheduling it up front
+        // instead of only ever looking one tick ahead.
+        let (mut world, a, b) = head_on_pair();
+        let mut scheduler = CollisionScheduler::new();
+        scheduler.add_members(&world, &[a, b]);
+
+        // Predicted collision time is t=4s (see head_on_pair/above tests).
+        for _ in 0..(5 * 60) {
+            scheduler.step(&mut world, world.dt);
+            world.integrate();
+        }
+
+        assert_eq!(world.get(a).unwrap().collision_count, 1);
+        assert_eq!(world.get(b).unwrap().collision_count, 1);
+    }
+}
+
+/// Applies gravity and schedules inter-particle collisions for a set of
+/// `World` slots. Holds no particle data itself; everything lives in the
+/// `World` it's given each tick.
+pub struct GravityHandler {
+    pub members: Vec<Handle>,
+    pub gravity: [f64; 2],
+    collisions: CollisionScheduler,
+    script: Option<script::ForceScript>,
+}
+
+impl GravityHandler {
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+            gravity: [0.0, 0.098 * 60.0],
+            collisions: CollisionScheduler::new(),
+            script: None,
+        }
+    }
+
+    /// Builds a handler whose gravity vector comes from a loaded `Scene`.
+    pub fn from_config(config: &scene::Scene) -> Self {
+        Self {
+            gravity: config.gravity,
+            ..Self::new()
+        }
+    }
+
+    /// Attaches a compiled rhai force script. Each fixed step it's
+    /// evaluated once per member particle to compute an extra acceleration
+    /// on top of gravity, e.g. wind, drag, attractors, or vortices.
+    pub fn with_script(self, script: script::ForceScript) -> Self {
+        Self {
+            script: Some(script),
+            ..self
+        }
+    }
+
+    pub fn spawn_one(&mut self, world: &mut World, point: [f64; 2]) -> Handle {
+        let handle = world.spawn(
+            Particle::new(point)
+                .with_velocity([
+                    rand::thread_rng().gen_range(-1.0..1.0),
+                    rand::thread_rng().gen_range(-1.0..1.0),
+                ])
+                .with_lifespan(rand::thread_rng().gen_range(0.0..5.0)),
+        );
+        self.add_members(world, &[handle]);
+        handle
+    }
+
+    /// Registers handles spawned elsewhere (e.g. by an `Emitter`) as
+    /// members, so they get gravity, scripted forces, and collision
+    /// detection from now on. Only schedules collisions for the newly added
+    /// handles instead of rebuilding the whole pair set.
+    pub fn add_members(&mut self, world: &World, new_members: &[Handle]) {
+        self.collisions.add_members(world, new_members);
+        self.members.extend_from_slice(new_members);
+    }
+
+    /// Drops any member whose particle already deactivated or whose slot
+    /// was recycled out from under it, then predicts and resolves
+    /// collisions for the rest, and applies this tick's gravity
+    /// acceleration. Does not integrate positions; call `World::integrate`
+    /// once all handlers/emitters have applied their forces for the tick.
+    pub fn step(&mut self, world: &mut World) {
+        self.members.retain(|&handle| world.is_alive(handle));
+        self.collisions.remove_dead(world);
+        self.collisions.step(world, world.dt);
+
+        let gravity = self.gravity;
+        let time = world.time;
+        for &handle in &self.members {
+            let Some(particle) = world.get(handle) else {
+                continue;
+            };
+            let force = self
+                .script
+                .as_mut()
+                .map(|script| script.eval(particle.point, particle.velocity, particle.age(), time));
+
+            let particle = world.get_mut(handle).unwrap();
+            particle.push(gravity);
+            if let Some(force) = force {
+                particle.push(force);
+            }
+        }
+    }
+
+    pub fn draw<G>(&self, world: &World, draw_state: &DrawState, transform: [[f64; 3]; 2], g: &mut G)
+    where
+        G: graphics::Graphics,
+    {
+        for &handle in &self.members {
+            if let Some(particle) = world.get(handle) {
+                particle.draw(draw_state, transform, g);
+            }
+        }
     }
 }
 
@@ -160,6 +681,13 @@ impl Solid {
         }
     }
 
+    /// Builds a solid from a scene file's `SolidConfig`.
+    pub fn from_config(config: &scene::SolidConfig) -> Self {
+        let mut solid = Self::new(config.geometry, config.radius);
+        solid.threshold = config.threshold;
+        solid
+    }
+
     pub fn run(&mut self) {}
 
     pub fn draw<G>(&mut self, draw_state: &DrawState, transform: [[f64; 3]; 2], g: &mut G)
@@ -177,113 +705,86 @@ impl Solid {
     }
 }
 
-pub struct ExplodingParticles {
-    pub particles: Vec<Arc<Mutex<Particle>>>,
-    pub origin: [f64; 2],
-    pub strength: f64,
-    pub fading: Duration,
+/// Falls back to the original hard-coded two-solid demo scene when
+/// `scene.toml` is missing, so the binary still runs without a config file.
+fn default_scene() -> scene::Scene {
+    toml::from_str(
+        r#"
+        gravity = [0.0, 5.88]
+        initial_particles = 10
+
+        [window]
+        width = 512
+        height = 512
+
+        [[solids]]
+        geometry = [221.0, 420.0, 500.0, 420.0]
+        radius = 10.0
+
+        [[solids]]
+        geometry = [45.0, 45.0, 240.0, 240.0]
+        radius = 10.0
+
+        [[emitters]]
+        origin = [0.0, 0.0]
+        strength = 2.0
+        "#,
+    )
+    .unwrap()
 }
 
-impl ExplodingParticles {
-    pub fn new() -> Self {
-        Self {
-            particles: Vec::new(),
-            origin: [0.0, 0.0],
-            strength: 0.0,
-            fading: Duration::from_millis(500),
-        }
-    }
-
-    pub fn with_origin(self, origin: [f64; 2]) -> Self {
-        Self { origin, ..self }
-    }
+fn main() {
+    println!("Hello, world!");
 
-    pub fn with_strength(self, strength: f64) -> Self {
-        Self { strength, ..self }
-    }
+    let config = scene::Scene::load("scene.toml").unwrap_or_else(|_| default_scene());
 
-    pub fn trigger(&mut self) {
-        for _ in 0..50 {
-            let rc = Arc::new(Mutex::new(Particle::new(self.origin).with_velocity([
-                rand::thread_rng().gen_range(-self.strength..self.strength),
-                rand::thread_rng().gen_range(-self.strength..self.strength),
-            ])));
-            self.particles.push(rc.clone());
+    let opengl = OpenGL::V4_5;
+    let mut window: PistonWindow =
+        WindowSettings::new("shapes", [config.window.width, config.window.height])
+            .exit_on_esc(true)
+            .graphics_api(opengl)
+            .build()
+            .unwrap();
+
+    let mut world = World::new();
+    let mut handler = GravityHandler::from_config(&config);
+    if let Some(source) = &config.force_script {
+        match script::ForceScript::compile(source) {
+            Ok(compiled) => handler = handler.with_script(compiled),
+            Err(err) => eprintln!("force_script failed to compile, ignoring: {err}"),
         }
-
-        self.particles.iter().for_each(|parti| {
-            let rc = parti.clone();
-
-            let (tx, rx) = oneshot::channel();
-
-            let this_duration = self.fading;
-            tokio::spawn(async move {
-                tokio::time::sleep(this_duration).await;
-                tx.send(rc).unwrap();
-            });
-
-            tokio::spawn(async move {
-                let part = rx.await.unwrap();
-
-                part.lock().unwrap().active = false;
-            });
-        });
     }
 
-    pub fn update(&mut self) {
-        self.particles.iter().for_each(|parti| {
-            let rc = parti.clone();
-            rc.lock().unwrap().run();
-        });
-        self.particles.retain(|part| part.lock().unwrap().active);
-    }
-
-    pub fn draw<G: graphics::Graphics>(
-        &self,
-        draw_state: &DrawState,
-        transform: [[f64; 3]; 2],
-        g: &mut G,
-    ) {
-        self.particles.iter().for_each(|part| {
-            let rc = part.clone();
-            let particle = rc.lock().unwrap();
-
-            particle.draw(draw_state, transform, g);
-        });
+    for _ in 0..config.initial_particles {
+        handler.spawn_one(
+            &mut world,
+            [
+                rand::thread_rng().gen::<f64>() * config.window.width as f64,
+                rand::thread_rng().gen::<f64>() * config.window.height as f64,
+            ],
+        );
     }
-}
-
-#[tokio::main]
-async fn main() {
-    println!("Hello, world!");
-
-    let opengl = OpenGL::V4_5;
-    let mut window: PistonWindow = WindowSettings::new("shapes", [512; 2])
-        .exit_on_esc(true)
-        .graphics_api(opengl)
-        .build()
-        .unwrap();
-
-    let mut handler = GravityHandler::new();
 
-    for _ in 0..10 {
-        handler.spawn_one([
-            rand::thread_rng().gen::<f64>() * 500.0,
-            rand::thread_rng().gen::<f64>() * 500.0,
-        ]);
-    }
+    let mut solids: Vec<Solid> = config.solids.iter().map(Solid::from_config).collect();
 
-    let mut explosion = ExplodingParticles::new().with_strength(2.0);
+    let mut explosion = config
+        .emitters
+        .first()
+        .map(emitter::Emitter::from_config)
+        .unwrap_or_else(|| {
+            emitter::Emitter::new().with_velocity(emitter::VelocityMode::UniformBox {
+                strength: 2.0,
+            })
+        })
+        .with_default_ramp(ColorRamp::fire());
 
     while let Some(e) = window.next() {
         if let Event::Input(test, test2) = &e {
             if let Input::Button(args) = test {
                 if args.state == ButtonState::Press {
                     println!("cliked");
-                    explosion.trigger();
-                    explosion.particles.iter().for_each(|arc| {
-                        handler.entities.push(arc.clone());
-                    });
+                    let spawned = explosion.trigger(&mut world);
+                    handler.add_members(&world, &spawned);
                 }
             }
         }
@@ -291,51 +792,45 @@ async fn main() {
         e.mouse_cursor(|take| {
             println!("test");
             /* explosion.origin = take;
-                        explosion.trigger();
-                        explosion.particles.iter().for_each(|arc| {
-                            handler.entities.push(arc.clone());
-                        });
+                        explosion.trigger(&mut world);
+                        handler.members.extend(explosion.members.iter().copied());
             */
             for _ in 0..10 {
-                //handler.spawn_one(take);
+                //handler.spawn_one(&mut world, take);
             }
         });
 
-        window.draw_2d(&e, |c, g, _| {
-            clear([1.0; 4], g);
-
-            let mut solid = Solid::new([221.0, 420.0, 500.0, 420.00], 10.0);
-            let mut sol2 = Solid::new([45.0, 45.0, 240.0, 240.0], 10.0);
-
-            // Line::new(color, 0.1).draw([45.0, 45.0, 46.0, 46.0], &c.draw_state, c.transform, g);
-            handler.entities.iter().for_each(|enti| {
-                let mut inner = enti.lock().unwrap();
-                let geometry = inner.get_geometry();
-                let acceleration = inner.acceleration;
-                let vel = inner.velocity;
-                if solid.is_colliding(geometry) {
-                    let stop = rand::thread_rng().gen_range(12..18) as f64 / 10.0;
-                    inner.push([0.0, -vel[1]]);
+        if let Some(args) = e.update_args() {
+            for _ in 0..world.take_steps(args.dt) {
+                for &handle in &handler.members {
+                    let Some(particle) = world.get(handle) else {
+                        continue;
+                    };
+                    let geometry = particle.get_geometry();
+                    let vel = particle.velocity;
+                    for solid in &solids {
+                        if solid.is_colliding(geometry) {
+                            world.get_mut(handle).unwrap().push([0.0, -vel[1]]);
+                        }
+                    }
                 }
 
-                if sol2.is_colliding(geometry) {
-                    inner.push([0.0, -vel[1]]);
-                }
-            });
-            handler.run();
-            explosion.update();
+                handler.step(&mut world);
+                let spawned = explosion.step(&mut world, world.dt);
+                handler.add_members(&world, &spawned);
+                world.integrate();
+            }
+        }
 
-            solid.draw(&c.draw_state, c.transform, g);
-            sol2.draw(&c.draw_state, c.transform, g);
+        window.draw_2d(&e, |c, g, _| {
+            clear([1.0; 4], g);
 
-            handler.draw(&c.draw_state, c.transform, g);
-            explosion.draw(&c.draw_state, c.transform, g);
-            /*one.clone()
-                .lock()
-                .unwrap()
-                .draw(&c.draw_state, c.transform, g);
+            solids
+                .iter_mut()
+                .for_each(|solid| solid.draw(&c.draw_state, c.transform, g));
 
-            */
+            handler.draw(&world, &c.draw_state, c.transform, g);
+            explosion.draw(&world, &c.draw_state, c.transform, g);
         });
     }
 }
@@ -0,0 +1,97 @@
+use rhai::{Array, Engine, Scope, AST};
+
+/// A user-supplied rhai expression compiled once at startup and evaluated
+/// every fixed physics step to compute an extra acceleration contribution
+/// for a particle, e.g. wind, drag, attractors, or vortices, without
+/// touching Rust. The script sees `px`, `py`, `vx`, `vy`, `age`, and `time`
+/// in scope and must evaluate to a two-element array `[ax, ay]`.
+pub struct ForceScript {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+}
+
+impl ForceScript {
+    pub fn compile(source: &str) -> Result<Self, Box<rhai::EvalAltResult>> {
+        let engine = Engine::new();
+        let ast = engine.compile(source)?;
+        Ok(Self {
+            engine,
+            ast,
+            scope: Scope::new(),
+        })
+    }
+
+    /// Evaluates the cached AST for one particle, returning `[0.0, 0.0]`
+    /// if the script errors or doesn't return a two-element array.
+    pub fn eval(&mut self, point: [f64; 2], velocity: [f64; 2], age: f64, time: f64) -> [f64; 2] {
+        self.scope.clear();
+        self.scope.push("px", point[0]);
+        self.scope.push("py", point[1]);
+        self.scope.push("vx", velocity[0]);
+        self.scope.push("vy", velocity[1]);
+        self.scope.push("age", age);
+        self.scope.push("time", time);
+
+        let result = self
+            .engine
+            .eval_ast_with_scope::<Array>(&mut self.scope, &self.ast);
+
+        match result {
+            Ok(values) if values.len() == 2 => {
+                [to_force_component(&values[0]), to_force_component(&values[1])]
+            }
+            _ => [0.0, 0.0],
+        }
+    }
+}
+
+/// Converts a script's returned `Dynamic` to `f64`, falling back to `0.0`
+/// only when it's neither a float nor an integer. `Dynamic::as_float`
+/// alone rejects rhai integer literals (e.g. `[0, -1]`) instead of
+/// widening them, which would otherwise silently zero out any whole-number
+/// force a user writes.
+fn to_force_component(value: &rhai::Dynamic) -> f64 {
+    value
+        .as_float()
+        .or_else(|_| value.as_int().map(|i| i as f64))
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_a_force_expression_using_the_particle_state() {
+        let mut script = ForceScript::compile("[vx * -0.1, 9.8]").unwrap();
+        let force = script.eval([0.0, 0.0], [2.0, 0.0], 0.0, 0.0);
+        assert_eq!(force, [-0.2, 9.8]);
+    }
+
+    #[test]
+    fn integer_literals_are_not_silently_coerced_to_zero() {
+        let mut script = ForceScript::compile("[0, -1]").unwrap();
+        let force = script.eval([0.0, 0.0], [0.0, 0.0], 0.0, 0.0);
+        assert_eq!(force, [0.0, -1.0]);
+    }
+
+    #[test]
+    fn compile_rejects_malformed_syntax() {
+        assert!(ForceScript::compile("[vx * ").is_err());
+    }
+
+    #[test]
+    fn eval_falls_back_to_zero_on_a_runtime_error() {
+        let mut script = ForceScript::compile("[undefined_variable, 0.0]").unwrap();
+        let force = script.eval([0.0, 0.0], [0.0, 0.0], 0.0, 0.0);
+        assert_eq!(force, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn eval_falls_back_to_zero_when_result_is_not_a_two_element_array() {
+        let mut script = ForceScript::compile("42").unwrap();
+        let force = script.eval([0.0, 0.0], [0.0, 0.0], 0.0, 0.0);
+        assert_eq!(force, [0.0, 0.0]);
+    }
+}
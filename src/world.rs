@@ -0,0 +1,171 @@
+use crate::Particle;
+
+/// A `World` slot tagged with the generation it was spawned at. Once the
+/// particle occupying `slot` dies and the slot is reused, its generation is
+/// bumped, so a `Handle` held onto past its particle's death (e.g. in a
+/// `GravityHandler`/`Emitter` member list that hasn't been pruned yet)
+/// reliably fails `World::get` instead of silently aliasing whatever new
+/// particle now lives there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u64,
+}
+
+impl Handle {
+    /// The raw pool slot this handle refers to. Exposed only so callers can
+    /// order/dedup handles (e.g. normalizing a collision pair); it carries
+    /// no meaning on its own without the matching generation.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+}
+
+/// Owns a contiguous pool of particles and steps them on a fixed timestep,
+/// independent of the render loop's frame rate. Dead slots go on a free
+/// list and are reused by the next spawn, so the pool never reallocates
+/// just from particles churning.
+pub struct World {
+    particles: Vec<Particle>,
+    generations: Vec<u64>,
+    active: Vec<usize>,
+    free: Vec<usize>,
+    pub dt: f64,
+    accumulator: f64,
+    /// Total simulated time elapsed, in seconds. Advances once per fixed
+    /// substep, independent of real/wall-clock time.
+    pub time: f64,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            particles: Vec::new(),
+            generations: Vec::new(),
+            active: Vec::new(),
+            free: Vec::new(),
+            dt: 1.0 / 60.0,
+            accumulator: 0.0,
+            time: 0.0,
+        }
+    }
+
+    /// Inserts `particle` into a free slot (or grows the pool) and returns a
+    /// `Handle` to it. Reusing a free slot bumps its generation, so any
+    /// stale `Handle` still pointing at that slot's previous occupant is
+    /// left holding a generation that no longer matches.
+    pub fn spawn(&mut self, particle: Particle) -> Handle {
+        let slot = if let Some(slot) = self.free.pop() {
+            self.generations[slot] += 1;
+            self.particles[slot] = particle;
+            slot
+        } else {
+            self.particles.push(particle);
+            self.generations.push(0);
+            self.particles.len() - 1
+        };
+        self.active.push(slot);
+        Handle {
+            slot,
+            generation: self.generations[slot],
+        }
+    }
+
+    /// Returns the particle `handle` points at, or `None` if its slot has
+    /// since been recycled for a different particle.
+    pub fn get(&self, handle: Handle) -> Option<&Particle> {
+        if self.generations[handle.slot] == handle.generation {
+            Some(&self.particles[handle.slot])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut Particle> {
+        if self.generations[handle.slot] == handle.generation {
+            Some(&mut self.particles[handle.slot])
+        } else {
+            None
+        }
+    }
+
+    /// Whether `handle` still points at a live particle, i.e. its slot
+    /// hasn't been recycled and the particle hasn't deactivated.
+    pub fn is_alive(&self, handle: Handle) -> bool {
+        self.get(handle).map(|p| p.active).unwrap_or(false)
+    }
+
+    /// Accumulates `elapsed` real seconds and returns how many fixed-`dt`
+    /// substeps the caller should run this frame.
+    pub fn take_steps(&mut self, elapsed: f64) -> usize {
+        self.accumulator += elapsed;
+        // A tiny tolerance keeps float rounding from dropping a step whose
+        // time has fully accumulated (e.g. 2.5 dt followed by 0.5 dt).
+        let steps = (self.accumulator / self.dt + 1e-9).floor();
+        self.accumulator = (self.accumulator - steps * self.dt).max(0.0);
+        steps as usize
+    }
+
+    /// Integrates every active particle by one fixed `dt` tick and recycles
+    /// any that deactivated (e.g. its lifespan expired) back onto the free
+    /// list.
+    pub fn integrate(&mut self) {
+        let dt = self.dt;
+        let mut i = 0;
+        while i < self.active.len() {
+            let slot = self.active[i];
+            let particle = &mut self.particles[slot];
+            particle.run(dt);
+            if particle.active {
+                i += 1;
+            } else {
+                self.active.swap_remove(i);
+                self.free.push(slot);
+            }
+        }
+        self.time += dt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reused_slots_invalidate_stale_handles() {
+        let mut world = World::new();
+        let first = world.spawn(Particle::new([0.0, 0.0]).with_lifespan(0.0));
+
+        // One fixed step: age advances to dt, past lifespan (0.0), so the
+        // particle deactivates and its slot is recycled.
+        world.integrate();
+        assert!(!world.is_alive(first));
+
+        let second = world.spawn(Particle::new([1.0, 1.0]));
+        assert!(world.is_alive(second));
+        // A handle obtained before the slot was recycled must not resolve
+        // to the particle that now occupies it.
+        assert!(!world.is_alive(first));
+        assert_eq!(world.get(second).unwrap().point, [1.0, 1.0]);
+    }
+
+    #[test]
+    fn take_steps_accumulates_fractional_frame_time() {
+        let mut world = World::new();
+        assert_eq!(world.take_steps(world.dt * 2.5), 2);
+        // The leftover half-step should carry over into the next call.
+        assert_eq!(world.take_steps(world.dt * 0.5), 1);
+    }
+
+    #[test]
+    fn dead_particles_are_recycled_not_leaked() {
+        let mut world = World::new();
+        let a = world.spawn(Particle::new([0.0, 0.0]).with_lifespan(0.0));
+        world.integrate();
+        assert!(!world.is_alive(a));
+
+        let b = world.spawn(Particle::new([2.0, 2.0]));
+        // The dead slot was reused rather than the pool growing.
+        assert_eq!(b.slot, a.slot);
+    }
+}